@@ -0,0 +1,136 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+const READ_BLOCK: usize = 8192;
+
+#[derive(Clone, serde::Serialize)]
+pub struct LogLine {
+    pub service: String,
+    pub line: String,
+}
+
+/// Handle to a background log-tailing thread. Dropping it leaves the thread
+/// running; call `stop` to join it.
+pub struct LogStreamHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LogStreamHandle {
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(t) = self.thread.take() {
+            let _ = t.join();
+        }
+    }
+}
+
+/// Tail without reading the whole file: read backward in blocks until `n`
+/// lines are collected.
+pub fn tail_lines(path: &Path, n: usize) -> std::io::Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if n == 0 || len == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut pos = len;
+    let mut newline_count = 0usize;
+    let mut buf: Vec<u8> = Vec::new();
+
+    while pos > 0 && newline_count <= n {
+        let read_size = READ_BLOCK.min(pos as usize);
+        pos -= read_size as u64;
+        file.seek(SeekFrom::Start(pos))?;
+        let mut chunk = vec![0u8; read_size];
+        file.read_exact(&mut chunk)?;
+        newline_count += chunk.iter().filter(|&&b| b == b'\n').count();
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+    }
+
+    let text = String::from_utf8_lossy(&buf);
+    let mut lines: Vec<&str> = text.lines().collect();
+    // If we didn't start reading at the beginning of the file, the first
+    // line we captured is likely a partial line continued from before `pos`.
+    if pos > 0 && lines.len() > n {
+        lines.remove(0);
+    }
+    let start = lines.len().saturating_sub(n);
+    Ok(lines[start..].iter().map(|s| s.to_string()).collect())
+}
+
+/// Spawn a background thread that follows `path` from its current end and
+/// emits each newly appended line to the frontend as `event_name`. Handles
+/// log rotation by restarting from the beginning if the file shrinks.
+pub fn start_stream(
+    app: AppHandle,
+    service: String,
+    path: PathBuf,
+    event_name: &'static str,
+) -> LogStreamHandle {
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_clone = stop.clone();
+
+    let thread = std::thread::spawn(move || {
+        let mut offset = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let mut carry = String::new();
+
+        while !stop_clone.load(Ordering::SeqCst) {
+            std::thread::sleep(POLL_INTERVAL);
+
+            let len = match std::fs::metadata(&path) {
+                Ok(m) => m.len(),
+                Err(_) => continue,
+            };
+
+            if len < offset {
+                // Rotated or truncated underneath us; start over.
+                offset = 0;
+                carry.clear();
+            }
+            if len == offset {
+                continue;
+            }
+
+            let mut file = match File::open(&path) {
+                Ok(f) => f,
+                Err(_) => continue,
+            };
+            if file.seek(SeekFrom::Start(offset)).is_err() {
+                continue;
+            }
+            let mut buf = Vec::new();
+            if file.read_to_end(&mut buf).is_err() {
+                continue;
+            }
+            offset = len;
+
+            carry.push_str(&String::from_utf8_lossy(&buf));
+            while let Some(idx) = carry.find('\n') {
+                let line = carry[..idx].trim_end_matches('\r').to_string();
+                carry.drain(..=idx);
+                let _ = app.emit(
+                    event_name,
+                    LogLine {
+                        service: service.clone(),
+                        line,
+                    },
+                );
+            }
+        }
+    });
+
+    LogStreamHandle {
+        stop,
+        thread: Some(thread),
+    }
+}
@@ -1,13 +1,17 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod log_stream;
 mod orchestrator;
 mod ports;
 mod settings;
+mod weed_tools;
 
+use log_stream::LogStreamHandle;
 use orchestrator::{Orchestrator, ServiceStatus};
 use settings::{DesktopBootstrap, WizardSettings};
 
 use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Mutex;
@@ -21,6 +25,7 @@ struct AppState {
     install_token: Option<String>,
     orchestrator: Option<Orchestrator>,
     desired_running: bool,
+    log_streams: HashMap<String, LogStreamHandle>,
 }
 
 #[derive(Serialize)]
@@ -38,6 +43,16 @@ struct UiState {
     install_dir: Option<PathBuf>,
 }
 
+fn shutdown_grace(guard: &AppState) -> std::time::Duration {
+    std::time::Duration::from_millis(
+        guard
+            .settings
+            .as_ref()
+            .map(|s| s.shutdown_grace_ms)
+            .unwrap_or(settings::DEFAULT_SHUTDOWN_GRACE_MS),
+    )
+}
+
 fn repo_root_from_exe() -> PathBuf {
     // Dev-oriented: executable lives under desktop/src-tauri/target/... so walk up to repo root.
     // In packaged builds this should be replaced with embedded Core.
@@ -234,9 +249,10 @@ async fn stop_services(
     state: tauri::State<'_, Mutex<AppState>>,
 ) -> Result<UiState, String> {
     let mut guard = state.lock().unwrap();
+    let grace = shutdown_grace(&guard);
     if let Some(orch) = guard.orchestrator.as_mut() {
-        orch.stop_core();
-        orch.stop_seaweed();
+        orch.stop_core(grace);
+        orch.stop_seaweed(grace);
     }
     guard.desired_running = false;
     Ok(ui_state_from_guard(&app, &mut guard))
@@ -291,15 +307,33 @@ async fn request_ui_exit(
 
     {
         let mut guard = state.lock().unwrap();
+        let grace = shutdown_grace(&guard);
         if let Some(orch) = guard.orchestrator.as_mut() {
-            orch.stop_core();
-            orch.stop_seaweed();
+            orch.stop_core(grace);
+            orch.stop_seaweed(grace);
         }
     }
     app.exit(0);
     Ok(())
 }
 
+fn log_path_for(settings: &WizardSettings, service: &str) -> Result<(PathBuf, &'static str), String> {
+    match service {
+        "core" => Ok((
+            settings.faceforge_home.join("logs").join("core.log"),
+            "core-log-line",
+        )),
+        "seaweed" => Ok((
+            settings.faceforge_home.join("logs").join("seaweed.log"),
+            "seaweed-log-line",
+        )),
+        other => Err(format!("Unknown log service: {other}")),
+    }
+}
+
+/// One-shot fallback kept for callers that just want a snapshot without
+/// subscribing to `start_log_stream`. Bounded to `lines` regardless of file
+/// size via the reverse-chunked tail in `log_stream`.
 #[tauri::command]
 fn read_core_log(app: tauri::AppHandle, lines: usize) -> Result<Vec<String>, String> {
     let state = app.state::<Mutex<AppState>>();
@@ -307,24 +341,315 @@ fn read_core_log(app: tauri::AppHandle, lines: usize) -> Result<Vec<String>, Str
     if let Some(s) = &guard.settings {
         let log_path = s.faceforge_home.join("logs").join("core.log");
         if !log_path.exists() {
-             return Ok(vec![format!("Log file not found at {:?}", log_path)]);
+            return Ok(vec![format!("Log file not found at {:?}", log_path)]);
         }
-        
-        // Simple tail implementation
-        // For large logs, this is inefficient (reading whole file), but sufficient for MVP rolling logs (10MB).
-        match std::fs::read_to_string(&log_path) {
-            Ok(content) => {
-                let all_lines: Vec<&str> = content.lines().collect();
-                let start = all_lines.len().saturating_sub(lines);
-                Ok(all_lines[start..].iter().map(|s| s.to_string()).collect())
-            }
-            Err(e) => Ok(vec![format!("Error reading log: {}", e)])
+
+        match log_stream::tail_lines(&log_path, lines) {
+            Ok(lines) => Ok(lines),
+            Err(e) => Ok(vec![format!("Error reading log: {}", e)]),
         }
     } else {
         Ok(vec!["Settings not loaded - cannot resolve log path.".into()])
     }
 }
 
+#[tauri::command]
+fn start_log_stream(
+    app: tauri::AppHandle,
+    state: tauri::State<Mutex<AppState>>,
+    service: String,
+) -> Result<(), String> {
+    let mut guard = state.lock().unwrap();
+    let settings = guard
+        .settings
+        .clone()
+        .ok_or_else(|| "Not configured".to_string())?;
+
+    if guard.log_streams.contains_key(&service) {
+        return Ok(());
+    }
+
+    let (log_path, event_name) = log_path_for(&settings, &service)?;
+    let handle = log_stream::start_stream(app, service.clone(), log_path, event_name);
+    guard.log_streams.insert(service, handle);
+    Ok(())
+}
+
+#[tauri::command]
+fn stop_log_stream(state: tauri::State<Mutex<AppState>>, service: String) -> Result<(), String> {
+    let mut guard = state.lock().unwrap();
+    if let Some(handle) = guard.log_streams.remove(&service) {
+        handle.stop();
+    }
+    Ok(())
+}
+
+#[tauri::command]
+async fn export_config(
+    state: tauri::State<'_, Mutex<AppState>>,
+    out_path: String,
+    redact: bool,
+) -> Result<(), String> {
+    let faceforge_home = {
+        let guard = state.lock().unwrap();
+        guard
+            .settings
+            .clone()
+            .ok_or_else(|| "Not configured".to_string())?
+            .faceforge_home
+    };
+
+    settings::export_config(
+        &faceforge_home,
+        std::path::Path::new(&out_path),
+        redact,
+        env!("CARGO_PKG_VERSION"),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn import_config(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+    archive_path: String,
+    faceforge_home: String,
+) -> Result<UiState, String> {
+    let new_home = PathBuf::from(faceforge_home);
+    let imported =
+        settings::import_config(std::path::Path::new(&archive_path), &new_home).map_err(|e| e.to_string())?;
+
+    let bootstrap = DesktopBootstrap {
+        faceforge_home: new_home.clone(),
+    };
+    save_bootstrap(&app, &bootstrap).map_err(|e| e.to_string())?;
+
+    let mut guard = state.lock().unwrap();
+
+    // Stop whatever the old settings had running before dropping the
+    // orchestrator: a bare `Child` isn't killed on Drop, and on Linux/macOS
+    // there's no Job Object to catch it, so skipping this orphans the old
+    // Core/SeaweedFS processes holding the old ports and FACEFORGE_HOME.
+    let grace = shutdown_grace(&guard);
+    if let Some(orch) = guard.orchestrator.as_mut() {
+        orch.stop_core(grace);
+        orch.stop_seaweed(grace);
+    }
+
+    guard.bootstrap = Some(bootstrap);
+    guard.install_token = settings::read_install_token(&new_home).ok();
+    guard.settings = Some(imported);
+    // The orchestrator's repo_root doesn't change, but its in-memory child
+    // handles are no longer relevant to a freshly imported home.
+    guard.orchestrator = None;
+
+    Ok(ui_state_from_guard(&app, &mut guard))
+}
+
+#[tauri::command]
+fn suggest_weed_path() -> Option<String> {
+    weed_tools::find_weed_on_path().map(|p| p.to_string_lossy().to_string())
+}
+
+#[tauri::command]
+async fn download_weed(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<AppState>>,
+) -> Result<String, String> {
+    let faceforge_home = {
+        let guard = state.lock().unwrap();
+        guard
+            .settings
+            .as_ref()
+            .map(|s| s.faceforge_home.clone())
+            .or_else(|| guard.bootstrap.as_ref().map(|b| b.faceforge_home.clone()))
+            .ok_or_else(|| "Not configured".to_string())?
+    };
+
+    let path = weed_tools::download_weed(app, &faceforge_home)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut guard = state.lock().unwrap();
+    if let Some(s) = guard.settings.as_mut() {
+        s.seaweed_weed_path = Some(path.clone());
+        let updated = s.clone();
+        settings::write_desktop_json(&faceforge_home, &updated).map_err(|e| e.to_string())?;
+    }
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+#[derive(Serialize)]
+struct Diagnostics {
+    app_version: String,
+    os: String,
+    arch: String,
+    faceforge_home: Option<String>,
+    core_port_configured: Option<u16>,
+    core_port_listening: bool,
+    seaweed_s3_port_configured: Option<u16>,
+    seaweed_s3_port_listening: bool,
+    core_json_parses: bool,
+    desktop_json_parses: bool,
+    status: Option<ServiceStatus>,
+    exiftool_on_path: bool,
+    weed_on_path: bool,
+    free_disk_space_bytes: Option<u64>,
+    recent_core_log: Vec<String>,
+    text_report: String,
+}
+
+fn is_on_path(bin: &str) -> bool {
+    let exe_name = if cfg!(windows) {
+        format!("{bin}.exe")
+    } else {
+        bin.to_string()
+    };
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(&exe_name).is_file()))
+        .unwrap_or(false)
+}
+
+fn free_disk_space_bytes(path: &std::path::Path) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        let output = std::process::Command::new("df").arg("-Pk").arg(path).output().ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let fields: Vec<&str> = text.lines().last()?.split_whitespace().collect();
+        let available_kb: u64 = fields.get(3)?.parse().ok()?;
+        Some(available_kb * 1024)
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+        let wide: Vec<u16> = path
+            .as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        let mut free_bytes: u64 = 0;
+        let ok = unsafe {
+            GetDiskFreeSpaceExW(wide.as_ptr(), &mut free_bytes, std::ptr::null_mut(), std::ptr::null_mut())
+        };
+        if ok != 0 {
+            Some(free_bytes)
+        } else {
+            None
+        }
+    }
+}
+
+/// Bug-report snapshot for the UI. Secrets are masked before this leaves Rust.
+#[tauri::command]
+fn collect_diagnostics(app: tauri::AppHandle, state: tauri::State<Mutex<AppState>>) -> Diagnostics {
+    let mut guard = state.lock().unwrap();
+
+    if guard.bootstrap.is_none() {
+        guard.bootstrap = load_bootstrap(&app);
+    }
+    if guard.orchestrator.is_none() {
+        guard.orchestrator = Some(Orchestrator::new(repo_root_from_exe()));
+    }
+    let faceforge_home = guard
+        .settings
+        .as_ref()
+        .map(|s| s.faceforge_home.clone())
+        .or_else(|| guard.bootstrap.as_ref().map(|b| b.faceforge_home.clone()));
+
+    let core_json_parses = faceforge_home
+        .as_ref()
+        .map(|home| settings::read_install_token(home).is_ok())
+        .unwrap_or(false);
+    let desktop_json_parses = faceforge_home
+        .as_ref()
+        .map(|home| settings::read_desktop_json(home).is_ok())
+        .unwrap_or(false);
+
+    let core_port_configured = guard.settings.as_ref().map(|s| s.core_port);
+    let seaweed_s3_port_configured = guard.settings.as_ref().and_then(|s| s.seaweed_s3_port);
+
+    let core_port_listening = core_port_configured
+        .map(|p| orchestrator::Orchestrator::is_port_listening("127.0.0.1", p))
+        .unwrap_or(false);
+    let seaweed_s3_port_listening = seaweed_s3_port_configured
+        .map(|p| orchestrator::Orchestrator::is_port_listening("127.0.0.1", p))
+        .unwrap_or(false);
+
+    let status = if let (Some(s), Some(o)) = (guard.settings.clone(), guard.orchestrator.as_mut()) {
+        let healthy = o.core_healthy(&s);
+        Some(o.status_snapshot(&s, healthy))
+    } else {
+        None
+    };
+
+    let recent_core_log = faceforge_home
+        .as_ref()
+        .map(|home| home.join("logs").join("core.log"))
+        .and_then(|path| log_stream::tail_lines(&path, 200).ok())
+        .unwrap_or_default();
+
+    let free_disk_space_bytes = faceforge_home.as_ref().and_then(|home| free_disk_space_bytes(home));
+
+    let exiftool_on_path = is_on_path("exiftool");
+    let weed_on_path = weed_tools::find_weed_on_path().is_some();
+
+    let text_report = format!(
+        "FaceForge Desktop Diagnostics\n\
+         app_version: {}\n\
+         os/arch: {}/{}\n\
+         faceforge_home: {}\n\
+         core_port: {:?} (listening={})\n\
+         seaweed_s3_port: {:?} (listening={})\n\
+         core.json parses: {}\n\
+         desktop.json parses: {}\n\
+         status: {:?}\n\
+         exiftool on PATH: {}\n\
+         weed on PATH: {}\n\
+         free disk space: {:?} bytes\n\
+         install_token: ****\n\
+         s3 keys: ****",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        faceforge_home
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| "<unconfigured>".to_string()),
+        core_port_configured,
+        core_port_listening,
+        seaweed_s3_port_configured,
+        seaweed_s3_port_listening,
+        core_json_parses,
+        desktop_json_parses,
+        status,
+        exiftool_on_path,
+        weed_on_path,
+        free_disk_space_bytes,
+    );
+
+    Diagnostics {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        faceforge_home: faceforge_home.map(|p| p.to_string_lossy().to_string()),
+        core_port_configured,
+        core_port_listening,
+        seaweed_s3_port_configured,
+        seaweed_s3_port_listening,
+        core_json_parses,
+        desktop_json_parses,
+        status,
+        exiftool_on_path,
+        weed_on_path,
+        free_disk_space_bytes,
+        recent_core_log,
+        text_report,
+    }
+}
+
 fn build_tray(app: &tauri::AppHandle) -> anyhow::Result<()> {
     use tauri::menu::{Menu, MenuItem};
     use tauri::tray::TrayIconBuilder;
@@ -363,9 +688,10 @@ fn build_tray(app: &tauri::AppHandle) -> anyhow::Result<()> {
                     {
                         let state = app.state::<Mutex<AppState>>();
                         if let Ok(mut guard) = state.lock() {
+                            let grace = shutdown_grace(&guard);
                             if let Some(orch) = guard.orchestrator.as_mut() {
-                                orch.stop_core();
-                                orch.stop_seaweed();
+                                orch.stop_core(grace);
+                                orch.stop_seaweed(grace);
                             }
                         };
                     }
@@ -386,8 +712,18 @@ fn build_tray(app: &tauri::AppHandle) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Single-instance callback: just bring the existing window forward.
+fn focus_existing_instance(app: &tauri::AppHandle, _argv: Vec<String>, _cwd: String) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
 fn main() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(focus_existing_instance))
         .plugin(tauri_plugin_opener::init())
         .manage(Mutex::new(AppState::default()))
         .setup(|app| {
@@ -435,9 +771,10 @@ fn main() {
                     api.prevent_close();
                     let state = window.app_handle().state::<Mutex<AppState>>();
                     if let Ok(mut guard) = state.lock() {
+                        let grace = shutdown_grace(&guard);
                         if let Some(orch) = guard.orchestrator.as_mut() {
-                            orch.stop_core();
-                            orch.stop_seaweed();
+                            orch.stop_core(grace);
+                            orch.stop_seaweed(grace);
                         }
                     }
                     window.app_handle().exit(0);
@@ -456,6 +793,13 @@ fn main() {
             request_exit,
             request_ui_exit,
             read_core_log,
+            start_log_stream,
+            stop_log_stream,
+            export_config,
+            import_config,
+            suggest_weed_path,
+            download_weed,
+            collect_diagnostics,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
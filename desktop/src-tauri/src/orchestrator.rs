@@ -1,30 +1,306 @@
-use crate::ports::{write_ports, RuntimePorts};
+use crate::ports::write_ports;
 use crate::settings::WizardSettings;
-use anyhow::Context;
+use std::collections::VecDeque;
 use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Read, Write};
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
-use std::net::{SocketAddr, TcpStream};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Bounded ring buffer of recent log lines, shared between the reader
+/// threads that fill it and `Orchestrator::recent_logs` callers.
+type LogRing = Arc<Mutex<VecDeque<String>>>;
+
+const LOG_RING_CAPACITY: usize = 500;
+
+fn new_log_ring() -> LogRing {
+    Arc::new(Mutex::new(VecDeque::with_capacity(LOG_RING_CAPACITY)))
+}
+
+fn push_ring_line(ring: &LogRing, line: String) {
+    let mut guard = ring.lock().unwrap();
+    if guard.len() >= LOG_RING_CAPACITY {
+        guard.pop_front();
+    }
+    guard.push_back(line);
+}
+
+fn ring_tail(ring: &LogRing, n: usize) -> Vec<String> {
+    let guard = ring.lock().unwrap();
+    let start = guard.len().saturating_sub(n);
+    guard.iter().skip(start).cloned().collect()
+}
+
+/// Tees `reader` to `ring` and `tee`, one thread per pipe so stdout and
+/// stderr don't stall each other. Reads raw bytes (not `read_line`) since a
+/// non-UTF-8 line would otherwise error the thread out and kill logging for
+/// good; the ring buffer just gets a lossy decode of it.
+fn spawn_tee_reader<R: Read + Send + 'static>(
+    reader: R,
+    ring: LogRing,
+    mut tee: std::fs::File,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut buf_reader = BufReader::new(reader);
+        let mut line = Vec::new();
+        loop {
+            line.clear();
+            match buf_reader.read_until(b'\n', &mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let _ = tee.write_all(&line);
+                    let text = String::from_utf8_lossy(&line);
+                    push_ring_line(&ring, text.trim_end_matches(['\r', '\n']).to_string());
+                }
+            }
+        }
+    })
+}
+
+/// Strip AppImage/Flatpak/Snap-rewritten PATH and LD_LIBRARY_PATH entries
+/// before spawning, so Core/exiftool get the host system's libraries.
+#[cfg(target_os = "linux")]
+mod linux_env {
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    const PATHLIST_VARS: &[&str] = &[
+        "PATH",
+        "LD_LIBRARY_PATH",
+        "GST_PLUGIN_SYSTEM_PATH",
+        "XDG_DATA_DIRS",
+    ];
+
+    fn detect_bundle_root() -> Option<PathBuf> {
+        if std::env::var_os("APPIMAGE").is_some() {
+            if let Some(dir) = std::env::var_os("APPDIR") {
+                return Some(PathBuf::from(dir));
+            }
+        }
+        if std::env::var_os("FLATPAK_ID").is_some() || Path::new("/.flatpak-info").exists() {
+            return Some(PathBuf::from("/app"));
+        }
+        if let Some(dir) = std::env::var_os("SNAP") {
+            return Some(PathBuf::from(dir));
+        }
+        None
+    }
+
+    /// Split a colon-separated path list, drop entries under `bundle_root`,
+    /// and de-duplicate, keeping the later (lower-priority) occurrence of a
+    /// duplicate rather than the first. Returns `None` if nothing is left.
+    pub fn normalize_pathlist(value: &str, bundle_root: &Path) -> Option<String> {
+        let mut kept: Vec<&str> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
+
+        for entry in value.split(':').rev() {
+            if entry.is_empty() || Path::new(entry).starts_with(bundle_root) {
+                continue;
+            }
+            if seen.insert(entry) {
+                kept.push(entry);
+            }
+        }
+        kept.reverse();
+
+        if kept.is_empty() {
+            None
+        } else {
+            Some(kept.join(":"))
+        }
+    }
+
+    pub fn normalize_command_env(cmd: &mut Command) {
+        let Some(bundle_root) = detect_bundle_root() else {
+            return;
+        };
+
+        for var in PATHLIST_VARS {
+            if let Ok(value) = std::env::var(var) {
+                match normalize_pathlist(&value, &bundle_root) {
+                    Some(cleaned) => {
+                        cmd.env(var, cleaned);
+                    }
+                    None => {
+                        cmd.env_remove(var);
+                    }
+                }
+            }
+        }
+
+        for (key, value) in std::env::vars() {
+            if key.starts_with("GTK_") && Path::new(&value).starts_with(&bundle_root) {
+                cmd.env_remove(key);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ServiceStatus {
     pub core_running: bool,
     pub core_healthy: bool,
     pub core_url: String,
+    pub core_last_error: Option<ServiceError>,
     pub seaweed_enabled: bool,
     pub seaweed_running: bool,
     pub seaweed_s3_port: Option<u16>,
-    pub seaweed_last_error: Option<String>,
+    pub seaweed_last_error: Option<ServiceError>,
+    pub last_log_tail: Vec<String>,
+    pub seaweed_last_log_tail: Vec<String>,
+}
+
+/// Structured reason a service failed to start, so the UI can offer
+/// remediation specific to the failure instead of parsing an error string.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "kind")]
+pub enum ServiceError {
+    BinaryNotFound { searched: Vec<PathBuf> },
+    InvalidExecutable { path: PathBuf, preview: String },
+    PortConflict { conflicts: Vec<(String, u16)> },
+    SpawnFailed { os_error: Option<i32>, hint: Option<String> },
+    ExitedImmediately { status: String, log_path: PathBuf },
+}
+
+impl std::fmt::Display for ServiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceError::BinaryNotFound { searched } => {
+                let looked = searched
+                    .iter()
+                    .map(|p| p.to_string_lossy().to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                write!(f, "binary not found. Looked for: {looked}")
+            }
+            ServiceError::InvalidExecutable { path, preview } => {
+                write!(f, "{path:?} is not a valid executable. Preview: {preview}")
+            }
+            ServiceError::PortConflict { conflicts } => {
+                let joined = conflicts
+                    .iter()
+                    .map(|(name, port)| format!("{name}:{port}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "cannot start because these ports already have listeners: {joined}")
+            }
+            ServiceError::SpawnFailed { os_error, hint } => {
+                write!(f, "spawn failed")?;
+                if let Some(code) = os_error {
+                    write!(f, " (os_error={code})")?;
+                }
+                if let Some(h) = hint {
+                    write!(f, ": {h}")?;
+                }
+                Ok(())
+            }
+            ServiceError::ExitedImmediately { status, log_path } => {
+                write!(f, "exited immediately ({status}). Check logs at {log_path:?}")
+            }
+        }
+    }
+}
+
+fn io_error_to_service_error(e: std::io::Error) -> ServiceError {
+    ServiceError::SpawnFailed {
+        os_error: e.raw_os_error(),
+        hint: Some(e.to_string()),
+    }
+}
+
+/// Job Object that kills every assigned child when closed, so a crashed
+/// orchestrator doesn't leave orphans behind.
+#[cfg(windows)]
+mod windows_job {
+    use std::mem::size_of;
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+        JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE, JOB_OBJECT_LIMIT_PROCESS_MEMORY,
+    };
+
+    pub struct JobHandle(HANDLE);
+
+    // SAFETY: the HANDLE is only ever used through &self/&mut self methods
+    // below, which serialize access to the underlying Win32 object.
+    unsafe impl Send for JobHandle {}
+
+    impl JobHandle {
+        pub fn new() -> Option<Self> {
+            let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+            if handle == 0 {
+                return None;
+            }
+
+            let job = Self(handle);
+            job.apply_limits(None);
+            Some(job)
+        }
+
+        /// Sets `JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE`, plus an optional
+        /// per-process memory cap (in MB) so a runaway child can't exhaust
+        /// the host.
+        fn apply_limits(&self, memory_limit_mb: Option<u32>) {
+            let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+            info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+
+            if let Some(mb) = memory_limit_mb {
+                info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+                info.ProcessMemoryLimit = (mb as usize) * 1024 * 1024;
+            }
+
+            unsafe {
+                SetInformationJobObject(
+                    self.0,
+                    JobObjectExtendedLimitInformation,
+                    &info as *const _ as *const _,
+                    size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                );
+            }
+        }
+
+        pub fn set_process_memory_limit_mb(&self, mb: u32) {
+            self.apply_limits(Some(mb));
+        }
+
+        pub fn assign(&self, child: &std::process::Child) {
+            use std::os::windows::io::AsRawHandle;
+            unsafe {
+                AssignProcessToJobObject(self.0, child.as_raw_handle() as HANDLE);
+            }
+        }
+    }
+
+    impl Drop for JobHandle {
+        fn drop(&mut self) {
+            unsafe {
+                CloseHandle(self.0);
+            }
+        }
+    }
 }
 
 pub struct Orchestrator {
     repo_root: PathBuf,
     core_child: Option<Child>,
     seaweed_child: Option<Child>,
-    last_seaweed_error: Option<String>,
+    last_seaweed_error: Option<ServiceError>,
+    last_core_error: Option<ServiceError>,
     last_core_start: Option<Instant>,
     core_restart_attempts: u32,
+    core_log_ring: LogRing,
+    seaweed_log_ring: LogRing,
+    // Separate Job Objects per service: JOB_OBJECT_LIMIT_PROCESS_MEMORY is a
+    // per-job cap applied to every process assigned to it, so sharing one
+    // job between Core and SeaweedFS would leak `core_max_memory_mb` onto
+    // SeaweedFS too.
+    #[cfg(windows)]
+    core_job: Option<windows_job::JobHandle>,
+    #[cfg(windows)]
+    seaweed_job: Option<windows_job::JobHandle>,
 }
 
 impl Orchestrator {
@@ -34,8 +310,15 @@ impl Orchestrator {
             core_child: None,
             seaweed_child: None,
             last_seaweed_error: None,
+            last_core_error: None,
             last_core_start: None,
             core_restart_attempts: 0,
+            core_log_ring: new_log_ring(),
+            seaweed_log_ring: new_log_ring(),
+            #[cfg(windows)]
+            core_job: windows_job::JobHandle::new(),
+            #[cfg(windows)]
+            seaweed_job: windows_job::JobHandle::new(),
         }
     }
 
@@ -82,7 +365,7 @@ impl Orchestrator {
         None
     }
 
-    fn resolve_core_sidecar(&self) -> anyhow::Result<PathBuf> {
+    fn resolve_core_sidecar(&self) -> Result<PathBuf, ServiceError> {
         // In dev builds we keep a copy at desktop/src-tauri/binaries.
         // In packaged builds Tauri may rename sidecars with a target triple.
         // We therefore try a small search strategy rather than assuming an exact filename.
@@ -100,7 +383,7 @@ impl Orchestrator {
                 .join("faceforge-core"),
         ];
 
-        let exe = std::env::current_exe()?;
+        let exe = std::env::current_exe().map_err(io_error_to_service_error)?;
         if let Some(dir) = exe.parent() {
             candidates.push(dir.join("faceforge-core.exe"));
             candidates.push(dir.join("faceforge-core"));
@@ -131,15 +414,13 @@ impl Orchestrator {
             }
         }
 
-        for c in candidates {
+        for c in &candidates {
             if c.exists() {
-                return Ok(c);
+                return Ok(c.clone());
             }
         }
 
-        anyhow::bail!(
-            "Core executable sidecar not found (and .venv missing). Looked in repo binaries and beside the desktop executable."
-        )
+        Err(ServiceError::BinaryNotFound { searched: candidates })
     }
 
     fn weed_candidates(&self, settings: &WizardSettings) -> Vec<PathBuf> {
@@ -218,20 +499,13 @@ impl Orchestrator {
             return Ok(());
         }
 
-        let result: anyhow::Result<()> = (|| {
+        let result: Result<(), ServiceError> = (|| {
 
         let weed = match self.resolve_weed_path(settings) {
             Some(p) => p,
             None => {
-                let candidates = self
-                    .weed_candidates(settings)
-                    .into_iter()
-                    .map(|p| p.to_string_lossy().to_string())
-                    .collect::<Vec<_>>();
-                anyhow::bail!(
-                    "SeaweedFS enabled but 'weed' binary was not found. Looked for: {}. If you're building from source, run scripts/ensure-seaweedfs.ps1 to download the official Windows x64 binary.",
-                    candidates.join("; ")
-                );
+                let searched = self.weed_candidates(settings);
+                return Err(ServiceError::BinaryNotFound { searched });
             }
         };
 
@@ -239,54 +513,73 @@ impl Orchestrator {
         {
             use std::io::Read;
             // Quick sanity check: avoid trying to spawn a placeholder text file.
-            let mut f = std::fs::File::open(&weed).with_context(|| format!("Failed to open weed binary at {:?}", &weed))?;
+            let mut f = std::fs::File::open(&weed).map_err(io_error_to_service_error)?;
             let mut buf = [0u8; 128];
             let n = f.read(&mut buf).unwrap_or(0);
             let slice = &buf[..n];
             let is_mz = slice.len() >= 2 && slice[0] == b'M' && slice[1] == b'Z';
             if !is_mz {
-                let preview = String::from_utf8_lossy(slice);
-                anyhow::bail!(
-                    "SeaweedFS weed binary at {:?} is not a valid Windows executable (missing MZ header). \
-If you're building from source, run scripts/ensure-seaweedfs.ps1 (it downloads the official Windows x64 weed.exe). \
-Preview: {}",
-                    &weed,
-                    preview.replace('\r', " ").replace('\n', " ")
-                );
+                let preview = String::from_utf8_lossy(slice).replace('\r', " ").replace('\n', " ");
+                return Err(ServiceError::InvalidExecutable { path: weed.clone(), preview });
             }
         }
 
-        let s3_port = settings
-            .seaweed_s3_port
-            .context("SeaweedFS enabled but seaweed_s3_port not set")?;
+        let s3_port = settings.seaweed_s3_port.ok_or_else(|| ServiceError::SpawnFailed {
+            os_error: None,
+            hint: Some("SeaweedFS enabled but seaweed_s3_port not set".to_string()),
+        })?;
 
-        // These are currently hardcoded in the command args below.
-        let master_port: u16 = 9333;
-        let volume_port: u16 = 8080;
-        let filer_port: u16 = 8888;
+        // Defaults match upstream `weed server`; overridden below when a
+        // conflict is found or the user opted into auto port selection.
+        let mut master_port: u16 = 9333;
+        let mut volume_port: u16 = 8080;
+        let mut filer_port: u16 = 8888;
 
         let data_dir = settings.faceforge_home.join("s3").join("seaweedfs");
-        std::fs::create_dir_all(&data_dir)?;
+        std::fs::create_dir_all(&data_dir).map_err(io_error_to_service_error)?;
 
         // Preflight port checks: if anything is already listening, SeaweedFS may fail or exit immediately.
-        let mut conflicts: Vec<String> = Vec::new();
-        for (name, port) in [
-            ("master", master_port),
-            ("volume", volume_port),
-            ("filer", filer_port),
-            ("s3", s3_port),
-        ] {
-            if Self::tcp_port_open("127.0.0.1", port, Duration::from_millis(120)) {
-                conflicts.push(format!("{name}:{port}"));
+        let mut needs_auto_alloc = settings.seaweed_auto_ports;
+        if !needs_auto_alloc {
+            for (_name, port) in [
+                ("master", master_port),
+                ("volume", volume_port),
+                ("filer", filer_port),
+            ] {
+                if Self::tcp_port_open("127.0.0.1", port, Duration::from_millis(120)) {
+                    needs_auto_alloc = true;
+                    break;
+                }
             }
         }
-        if !conflicts.is_empty() {
-            anyhow::bail!(
-                "SeaweedFS cannot start because these ports already have listeners: {}",
-                conflicts.join(", ")
-            );
+        // The S3 port is user-configured (surfaced in the wizard) and isn't
+        // auto-allocated, so a conflict there is still fatal.
+        if Self::tcp_port_open("127.0.0.1", s3_port, Duration::from_millis(120)) {
+            return Err(ServiceError::PortConflict {
+                conflicts: vec![("s3".to_string(), s3_port)],
+            });
         }
 
+        if needs_auto_alloc {
+            let allocated = Self::allocate_free_ports(3).map_err(io_error_to_service_error)?;
+            master_port = allocated[0];
+            volume_port = allocated[1];
+            filer_port = allocated[2];
+        }
+
+        // Record the resolved ports before spawning so Core and any S3
+        // clients can discover them even if the wizard-configured defaults
+        // were overridden above.
+        let mut runtime_ports = crate::ports::read_ports(&settings.faceforge_home);
+        runtime_ports.seaweed_s3 = Some(s3_port);
+        runtime_ports.seaweed_master = Some(master_port);
+        runtime_ports.seaweed_volume = Some(volume_port);
+        runtime_ports.seaweed_filer = Some(filer_port);
+        write_ports(&settings.faceforge_home, &runtime_ports).map_err(|e| ServiceError::SpawnFailed {
+            os_error: None,
+            hint: Some(e.to_string()),
+        })?;
+
         let mut cmd = Command::new(&weed);
         cmd.arg("server")
             .arg("-ip=127.0.0.1")
@@ -299,13 +592,17 @@ Preview: {}",
             .current_dir(&settings.faceforge_home)
             .stdin(Stdio::null());
 
-        // Log to FACEFORGE_HOME/logs/seaweed.log for debugging.
+        // Log to FACEFORGE_HOME/logs/seaweed.log for debugging, while also
+        // piping stdout/stderr so we can keep an in-memory tail for the
+        // Status UI without reopening the file.
         let logs_dir = settings.faceforge_home.join("logs");
-        std::fs::create_dir_all(&logs_dir)?;
+        std::fs::create_dir_all(&logs_dir).map_err(io_error_to_service_error)?;
         let log_path = logs_dir.join("seaweed.log");
-        let out = Self::prepare_log_file(&log_path, settings.max_log_size_mb)?;
-        let err = out.try_clone()?;
-        cmd.stdout(Stdio::from(out)).stderr(Stdio::from(err));
+        let tee_out = Self::prepare_log_file(&log_path, settings.max_log_size_mb).map_err(|e| {
+            ServiceError::SpawnFailed { os_error: None, hint: Some(e.to_string()) }
+        })?;
+        let tee_err = tee_out.try_clone().map_err(io_error_to_service_error)?;
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
         #[cfg(windows)]
         {
@@ -314,41 +611,22 @@ Preview: {}",
             cmd.creation_flags(0x00000200 | 0x08000000);
         }
 
-        fn format_spawn_error(e: &std::io::Error) -> String {
-            let raw = e.raw_os_error();
-            let mut s = format!("{}", e);
-            s.push_str(&format!(" (kind={:?}", e.kind()));
-            if let Some(code) = raw {
-                s.push_str(&format!(", os_error={}", code));
-                #[cfg(windows)]
-                {
-                    // Common Windows causes:
-                    // 2 = file not found, 5 = access denied, 193 = bad exe, 126 = missing DLL/module.
-                    if code == 2 {
-                        s.push_str(", hint=path not found");
-                    } else if code == 5 {
-                        s.push_str(", hint=access denied (AV/quarantine/permissions)");
-                    } else if code == 193 {
-                        s.push_str(", hint=not a valid Windows executable (wrong arch?)");
-                    } else if code == 126 {
-                        s.push_str(", hint=missing dependency/DLL (VC runtime?)");
-                    }
-                }
-            }
-            s.push(')');
-            s
+        #[cfg(target_os = "linux")]
+        linux_env::normalize_command_env(&mut cmd);
+
+        let mut child = cmd.spawn().map_err(io_error_to_service_error)?;
+
+        #[cfg(windows)]
+        if let Some(job) = &self.seaweed_job {
+            job.assign(&child);
         }
 
-        let child = cmd.spawn().map_err(|e| {
-            let msg = format!(
-                "Failed to start SeaweedFS (weed={:?}, s3_port={}, log={:?}). Spawn error: {}",
-                &weed,
-                s3_port,
-                log_path,
-                format_spawn_error(&e)
-            );
-            anyhow::anyhow!(msg)
-        })?;
+        if let Some(stdout) = child.stdout.take() {
+            spawn_tee_reader(stdout, self.seaweed_log_ring.clone(), tee_out);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_tee_reader(stderr, self.seaweed_log_ring.clone(), tee_err);
+        }
 
         self.seaweed_child = Some(child);
 
@@ -362,11 +640,10 @@ Preview: {}",
             if let Some(child) = &mut self.seaweed_child {
                 if let Ok(Some(status)) = child.try_wait() {
                     self.seaweed_child = None;
-                    anyhow::bail!(
-                        "SeaweedFS exited immediately ({:?}). Check logs at {:?}",
-                        status,
-                        log_path
-                    );
+                    return Err(ServiceError::ExitedImmediately {
+                        status: status.to_string(),
+                        log_path,
+                    });
                 }
             }
             std::thread::sleep(Duration::from_millis(100));
@@ -381,18 +658,54 @@ Preview: {}",
                 Ok(())
             }
             Err(e) => {
-                // Preserve for Status UI; string is fine for MVP.
-                self.last_seaweed_error = Some(e.to_string());
-                Err(e)
+                let message = e.to_string();
+                self.last_seaweed_error = Some(e);
+                Err(anyhow::anyhow!("Failed to start SeaweedFS: {message}"))
             }
         }
     }
 
-    pub fn stop_seaweed(&mut self) {
+    pub fn stop_seaweed(&mut self, grace: Duration) {
         if let Some(mut c) = self.seaweed_child.take() {
-            let _ = c.kill();
-            let _ = c.wait();
+            Self::graceful_stop(&mut c, grace);
+        }
+    }
+
+    /// Ask nicely, wait out the grace period, then kill if it's still alive.
+    fn graceful_stop(child: &mut Child, grace: Duration) {
+        #[cfg(unix)]
+        {
+            // SAFETY: `child.id()` is a valid pid for as long as we hold `child`.
+            unsafe {
+                libc::kill(child.id() as libc::pid_t, libc::SIGTERM);
+            }
+        }
+        #[cfg(windows)]
+        {
+            use windows_sys::Win32::System::Console::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT};
+            // Children are spawned with CREATE_NEW_PROCESS_GROUP, so this
+            // targets only the child's process group, not our own.
+            unsafe {
+                GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, child.id());
+            }
         }
+
+        let deadline = Instant::now() + grace;
+        loop {
+            match child.try_wait() {
+                Ok(Some(_)) => return,
+                Ok(None) => {
+                    if Instant::now() >= deadline {
+                        break;
+                    }
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                Err(_) => return,
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
     }
 
     pub fn start_core(&mut self, settings: &WizardSettings) -> anyhow::Result<()> {
@@ -400,14 +713,17 @@ Preview: {}",
             return Ok(());
         }
 
-        // Ensure ports.json exists for Core's `python -m faceforge_core`.
-        write_ports(
-            &settings.faceforge_home,
-            &RuntimePorts {
-                core: Some(settings.core_port),
-                seaweed_s3: settings.seaweed_s3_port,
-            },
-        )?;
+        let result: Result<(), ServiceError> = (|| {
+
+        // Ensure ports.json exists for Core's `python -m faceforge_core`,
+        // preserving any SeaweedFS ports already recorded by a prior start.
+        let mut runtime_ports = crate::ports::read_ports(&settings.faceforge_home);
+        runtime_ports.core = Some(settings.core_port);
+        runtime_ports.seaweed_s3 = settings.seaweed_s3_port;
+        write_ports(&settings.faceforge_home, &runtime_ports).map_err(|e| ServiceError::SpawnFailed {
+            os_error: None,
+            hint: Some(e.to_string()),
+        })?;
 
         // Strategy: prefer venv python (dev mode), fallback to bundled executable sidecar.
         let (bin_path, args, work_dir) = if let Some(python) = self.find_venv_python() {
@@ -424,7 +740,7 @@ Preview: {}",
         };
 
         let mut cmd = Command::new(&bin_path);
-        
+
         if let Some(wd) = work_dir {
             cmd.current_dir(wd)
                 // Avoid relying on editable install in dev: point PYTHONPATH at core/src.
@@ -439,13 +755,17 @@ Preview: {}",
             .env("FACEFORGE_BIND", "127.0.0.1")
             .stdin(Stdio::null());
 
-        // Capture output for diagnosis (Core can fail fast in dev if deps are missing).
+        // Capture output for diagnosis (Core can fail fast in dev if deps are
+        // missing), and pipe it so we can keep an in-memory tail for the
+        // Status UI alongside the on-disk log.
         let logs_dir = settings.faceforge_home.join("logs");
-        std::fs::create_dir_all(&logs_dir)?;
+        std::fs::create_dir_all(&logs_dir).map_err(io_error_to_service_error)?;
         let log_path = logs_dir.join("core.log");
-        let out = Self::prepare_log_file(&log_path, settings.max_log_size_mb)?;
-        let err = out.try_clone()?;
-        cmd.stdout(Stdio::from(out)).stderr(Stdio::from(err));
+        let tee_out = Self::prepare_log_file(&log_path, settings.max_log_size_mb).map_err(|e| {
+            ServiceError::SpawnFailed { os_error: None, hint: Some(e.to_string()) }
+        })?;
+        let tee_err = tee_out.try_clone().map_err(io_error_to_service_error)?;
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
         #[cfg(windows)]
         {
@@ -454,16 +774,48 @@ Preview: {}",
             cmd.creation_flags(0x00000200 | 0x08000000);
         }
 
-        self.core_child = Some(cmd.spawn().context(format!("Failed to start Core: {:?}", bin_path))?);
+        #[cfg(target_os = "linux")]
+        linux_env::normalize_command_env(&mut cmd);
+
+        let mut child = cmd.spawn().map_err(io_error_to_service_error)?;
+
+        #[cfg(windows)]
+        if let Some(job) = &self.core_job {
+            job.assign(&child);
+            if let Some(mb) = settings.core_max_memory_mb {
+                job.set_process_memory_limit_mb(mb);
+            }
+        }
+
+        if let Some(stdout) = child.stdout.take() {
+            spawn_tee_reader(stdout, self.core_log_ring.clone(), tee_out);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_tee_reader(stderr, self.core_log_ring.clone(), tee_err);
+        }
+
+        self.core_child = Some(child);
         self.last_core_start = Some(Instant::now());
         self.core_restart_attempts = 0;
         Ok(())
+        })();
+
+        match result {
+            Ok(()) => {
+                self.last_core_error = None;
+                Ok(())
+            }
+            Err(e) => {
+                let message = e.to_string();
+                self.last_core_error = Some(e);
+                Err(anyhow::anyhow!("Failed to start Core: {message}"))
+            }
+        }
     }
 
-    pub fn stop_core(&mut self) {
+    pub fn stop_core(&mut self, grace: Duration) {
         if let Some(mut c) = self.core_child.take() {
-            let _ = c.kill();
-            let _ = c.wait();
+            Self::graceful_stop(&mut c, grace);
         }
     }
 
@@ -475,6 +827,24 @@ Preview: {}",
         TcpStream::connect_timeout(&addr, timeout).is_ok()
     }
 
+    /// Bind `n` ephemeral listeners to let the OS pick free ports, then drop
+    /// them right before returning (there's still a bind-then-spawn race, but
+    /// this keeps the window as small as it can be).
+    fn allocate_free_ports(n: usize) -> std::io::Result<Vec<u16>> {
+        let mut listeners = Vec::with_capacity(n);
+        for _ in 0..n {
+            listeners.push(TcpListener::bind("127.0.0.1:0")?);
+        }
+        listeners.iter().map(|l| l.local_addr().map(|a| a.port())).collect()
+    }
+
+    /// Re-probe whether something is listening on `port`. Exposed for
+    /// diagnostics, which needs to flag conflicts independently of a
+    /// specific service's configured port.
+    pub fn is_port_listening(host: &str, port: u16) -> bool {
+        Self::tcp_port_open(host, port, Duration::from_millis(250))
+    }
+
     pub fn core_healthy(&self, settings: &WizardSettings) -> bool {
         // Basic health heuristic for Sprint 12: local port accept.
         Self::tcp_port_open("127.0.0.1", settings.core_port, Duration::from_millis(250))
@@ -510,7 +880,7 @@ Preview: {}",
         // If unhealthy for too long after start, restart once.
         if let Some(t0) = self.last_core_start {
             if t0.elapsed() > Duration::from_secs(10) {
-                self.stop_core();
+                self.stop_core(Duration::from_millis(settings.shutdown_grace_ms));
                 let _ = self.start_core(settings);
                 self.last_core_start = Some(Instant::now());
                 self.core_restart_attempts = 1;
@@ -518,16 +888,29 @@ Preview: {}",
         }
     }
 
+    /// Recent lines teed off a service's stdout/stderr as it runs, without
+    /// needing to reopen the log file. `service` is `"core"` or `"seaweed"`.
+    pub fn recent_logs(&self, service: &str, n: usize) -> Vec<String> {
+        match service {
+            "core" => ring_tail(&self.core_log_ring, n),
+            "seaweed" => ring_tail(&self.seaweed_log_ring, n),
+            _ => Vec::new(),
+        }
+    }
+
     pub fn status_snapshot(&mut self, settings: &WizardSettings, core_healthy: bool) -> ServiceStatus {
         let core_url = format!("http://127.0.0.1:{}", settings.core_port);
         ServiceStatus {
             core_running: self.is_core_running(),
             core_healthy,
             core_url,
+            core_last_error: self.last_core_error.clone(),
             seaweed_enabled: settings.seaweed_enabled,
             seaweed_running: self.is_seaweed_running(),
             seaweed_s3_port: settings.seaweed_s3_port,
             seaweed_last_error: self.last_seaweed_error.clone(),
+            last_log_tail: self.recent_logs("core", 20),
+            seaweed_last_log_tail: self.recent_logs("seaweed", 20),
         }
     }
 }
@@ -6,18 +6,34 @@ use std::path::{Path, PathBuf};
 pub struct RuntimePorts {
     pub core: Option<u16>,
     pub seaweed_s3: Option<u16>,
+    pub seaweed_master: Option<u16>,
+    pub seaweed_volume: Option<u16>,
+    pub seaweed_filer: Option<u16>,
 }
 
 pub fn ports_path(faceforge_home: &Path) -> PathBuf {
     faceforge_home.join("config").join("ports.json")
 }
 
+/// Reads the last-written runtime ports, e.g. so a caller updating one
+/// service's ports doesn't clobber another's. Defaults to all-`None` if the
+/// file is missing or unreadable.
+pub fn read_ports(faceforge_home: &Path) -> RuntimePorts {
+    fs::read_to_string(ports_path(faceforge_home))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
 pub fn write_ports(faceforge_home: &Path, ports: &RuntimePorts) -> anyhow::Result<()> {
     let config_dir = faceforge_home.join("config");
     fs::create_dir_all(&config_dir)?;
     let payload = serde_json::json!({
         "core": ports.core,
-        "seaweed_s3": ports.seaweed_s3
+        "seaweed_s3": ports.seaweed_s3,
+        "seaweed_master": ports.seaweed_master,
+        "seaweed_volume": ports.seaweed_volume,
+        "seaweed_filer": ports.seaweed_filer
     });
     fs::write(ports_path(faceforge_home), serde_json::to_vec_pretty(&payload)?)?;
     Ok(())
@@ -1,8 +1,10 @@
 use base64::engine::general_purpose::URL_SAFE_NO_PAD;
 use base64::Engine;
+use anyhow::Context;
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +12,9 @@ pub struct DesktopBootstrap {
     pub faceforge_home: PathBuf,
 }
 
+pub const DEFAULT_SHUTDOWN_GRACE_MS: u64 = 5_000;
+pub const DEFAULT_MAX_LOG_SIZE_MB: u32 = 20;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WizardSettings {
     pub faceforge_home: PathBuf,
@@ -17,6 +22,20 @@ pub struct WizardSettings {
     pub seaweed_enabled: bool,
     pub seaweed_s3_port: Option<u16>,
     pub seaweed_weed_path: Option<PathBuf>,
+    /// How long to wait after a graceful-stop request before escalating to a
+    /// hard kill. See `Orchestrator::stop_core`/`stop_seaweed`.
+    pub shutdown_grace_ms: u64,
+    /// Optional cap (in MB) applied to Core's Windows Job Object so a
+    /// runaway process can't exhaust the host. Ignored on other platforms.
+    pub core_max_memory_mb: Option<u32>,
+    /// Let the orchestrator pick free ports for SeaweedFS's master/volume/filer
+    /// subservices instead of the fixed 9333/8080/8888 defaults. Useful when
+    /// those defaults collide with another service on the host.
+    pub seaweed_auto_ports: bool,
+    /// When Core/SeaweedFS is started and `core.log`/`seaweed.log` is larger
+    /// than this size, it is rotated to `*.log.1`; the size is not checked
+    /// while the service runs. See `Orchestrator::prepare_log_file`.
+    pub max_log_size_mb: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -146,7 +165,11 @@ pub fn write_desktop_json(faceforge_home: &Path, settings: &WizardSettings) -> a
         "core_port": settings.core_port,
         "seaweed_enabled": settings.seaweed_enabled,
         "seaweed_s3_port": settings.seaweed_s3_port,
-        "seaweed_weed_path": settings.seaweed_weed_path
+        "seaweed_weed_path": settings.seaweed_weed_path,
+        "shutdown_grace_ms": settings.shutdown_grace_ms,
+        "core_max_memory_mb": settings.core_max_memory_mb,
+        "seaweed_auto_ports": settings.seaweed_auto_ports,
+        "max_log_size_mb": settings.max_log_size_mb
     });
     fs::write(desktop_json_path(faceforge_home), serde_json::to_vec_pretty(&payload)?)?;
     Ok(())
@@ -169,6 +192,23 @@ pub fn read_desktop_json(faceforge_home: &Path) -> anyhow::Result<WizardSettings
             .unwrap_or(false),
         seaweed_s3_port: v.get("seaweed_s3_port").and_then(|x| x.as_u64()).map(|n| n as u16),
         seaweed_weed_path: v.get("seaweed_weed_path").and_then(|x| x.as_str()).map(PathBuf::from),
+        shutdown_grace_ms: v
+            .get("shutdown_grace_ms")
+            .and_then(|x| x.as_u64())
+            .unwrap_or(DEFAULT_SHUTDOWN_GRACE_MS),
+        core_max_memory_mb: v
+            .get("core_max_memory_mb")
+            .and_then(|x| x.as_u64())
+            .map(|n| n as u32),
+        seaweed_auto_ports: v
+            .get("seaweed_auto_ports")
+            .and_then(|x| x.as_bool())
+            .unwrap_or(false),
+        max_log_size_mb: v
+            .get("max_log_size_mb")
+            .and_then(|x| x.as_u64())
+            .map(|n| n as u32)
+            .unwrap_or(DEFAULT_MAX_LOG_SIZE_MB),
     })
 }
 
@@ -181,3 +221,180 @@ pub fn read_install_token(faceforge_home: &Path) -> anyhow::Result<String> {
     }
     Ok(token)
 }
+
+/// Prefix under which `config/core.json` and `config/desktop.json` are
+/// stored inside an exported archive.
+const CONFIG_ARCHIVE_PREFIX: &str = "config/";
+const REDACTED_MARKER: &str = "REDACTED";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigArchiveManifest {
+    pub app_version: String,
+    pub core_port: u16,
+    pub seaweed_enabled: bool,
+    pub redacted: bool,
+}
+
+/// Zip up core.json, desktop.json, and a manifest. Scrubs the install token
+/// and S3 keys when `redact` is set.
+pub fn export_config(
+    faceforge_home: &Path,
+    out_path: &Path,
+    redact: bool,
+    app_version: &str,
+) -> anyhow::Result<()> {
+    let wizard_settings = read_desktop_json(faceforge_home)?;
+
+    let mut core_json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(core_json_path(faceforge_home))?)?;
+    let desktop_json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(desktop_json_path(faceforge_home))?)?;
+
+    if redact {
+        if let Some(token) = core_json.pointer_mut("/auth/install_token") {
+            *token = serde_json::Value::String(REDACTED_MARKER.to_string());
+        }
+        if let Some(access) = core_json.pointer_mut("/storage/s3/access_key") {
+            *access = serde_json::Value::Null;
+        }
+        if let Some(secret) = core_json.pointer_mut("/storage/s3/secret_key") {
+            *secret = serde_json::Value::Null;
+        }
+    }
+
+    let manifest = ConfigArchiveManifest {
+        app_version: app_version.to_string(),
+        core_port: wizard_settings.core_port,
+        seaweed_enabled: wizard_settings.seaweed_enabled,
+        redacted: redact,
+    };
+
+    let file = fs::File::create(out_path)
+        .with_context(|| format!("Failed to create archive at {out_path:?}"))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options: zip::write::FileOptions<()> =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+
+    zip.start_file(format!("{CONFIG_ARCHIVE_PREFIX}core.json"), options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&core_json)?)?;
+
+    zip.start_file(format!("{CONFIG_ARCHIVE_PREFIX}desktop.json"), options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&desktop_json)?)?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Unzip an exported archive into `new_faceforge_home`, rewriting
+/// `faceforge_home` to the new machine and refreshing any redacted secrets.
+pub fn import_config(archive_path: &Path, new_faceforge_home: &Path) -> anyhow::Result<WizardSettings> {
+    let file = fs::File::open(archive_path)
+        .with_context(|| format!("Failed to open archive at {archive_path:?}"))?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let mut core_json: Option<serde_json::Value> = None;
+    let mut desktop_json: Option<serde_json::Value> = None;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        if entry.is_dir() || !entry.name().starts_with(CONFIG_ARCHIVE_PREFIX) {
+            continue;
+        }
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents)?;
+        if entry.name().ends_with("core.json") {
+            core_json = Some(serde_json::from_str(&contents)?);
+        } else if entry.name().ends_with("desktop.json") {
+            desktop_json = Some(serde_json::from_str(&contents)?);
+        }
+    }
+
+    let mut core_json = core_json.context("archive is missing config/core.json")?;
+    let mut desktop_json = desktop_json.context("archive is missing config/desktop.json")?;
+
+    desktop_json["faceforge_home"] =
+        serde_json::Value::String(new_faceforge_home.to_string_lossy().to_string());
+
+    let settings = WizardSettings {
+        faceforge_home: new_faceforge_home.to_path_buf(),
+        core_port: desktop_json
+            .get("core_port")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(43210) as u16,
+        seaweed_enabled: desktop_json
+            .get("seaweed_enabled")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        seaweed_s3_port: desktop_json
+            .get("seaweed_s3_port")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as u16),
+        seaweed_weed_path: desktop_json
+            .get("seaweed_weed_path")
+            .and_then(|v| v.as_str())
+            .map(PathBuf::from),
+        shutdown_grace_ms: desktop_json
+            .get("shutdown_grace_ms")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(DEFAULT_SHUTDOWN_GRACE_MS),
+        core_max_memory_mb: desktop_json
+            .get("core_max_memory_mb")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as u32),
+        seaweed_auto_ports: desktop_json
+            .get("seaweed_auto_ports")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        max_log_size_mb: desktop_json
+            .get("max_log_size_mb")
+            .and_then(|v| v.as_u64())
+            .map(|n| n as u32)
+            .unwrap_or(DEFAULT_MAX_LOG_SIZE_MB),
+    };
+
+    let token_redacted = core_json
+        .pointer("/auth/install_token")
+        .and_then(|t| t.as_str())
+        .map(|t| t == REDACTED_MARKER)
+        .unwrap_or(false);
+    let s3_keys_redacted = core_json
+        .pointer("/storage/s3/access_key")
+        .map(|v| v.is_null())
+        .unwrap_or(false)
+        || core_json
+            .pointer("/storage/s3/secret_key")
+            .map(|v| v.is_null())
+            .unwrap_or(false);
+
+    // Patch only the scrubbed fields in place rather than calling
+    // write_core_json wholesale, which would also reset storage.s3's
+    // bucket/region/endpoint_url/use_ssl and paths/tools to wizard defaults,
+    // throwing away whatever the user had configured before export.
+    if token_redacted {
+        if let Some(token) = core_json.pointer_mut("/auth/install_token") {
+            *token = serde_json::Value::String(generate_install_token());
+        }
+    }
+    if s3_keys_redacted {
+        let mut access = [0u8; 16];
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut access);
+        rand::thread_rng().fill_bytes(&mut secret);
+        if let Some(key) = core_json.pointer_mut("/storage/s3/access_key") {
+            *key = serde_json::Value::String(URL_SAFE_NO_PAD.encode(access));
+        }
+        if let Some(key) = core_json.pointer_mut("/storage/s3/secret_key") {
+            *key = serde_json::Value::String(URL_SAFE_NO_PAD.encode(secret));
+        }
+    }
+
+    fs::create_dir_all(new_faceforge_home.join("config"))?;
+    fs::write(core_json_path(new_faceforge_home), serde_json::to_vec_pretty(&core_json)?)?;
+
+    write_desktop_json(new_faceforge_home, &settings)?;
+
+    Ok(settings)
+}
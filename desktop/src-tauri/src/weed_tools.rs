@@ -0,0 +1,182 @@
+use anyhow::Context;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Emitter};
+
+#[derive(Clone, serde::Serialize)]
+pub struct DownloadProgress {
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+struct ReleaseAsset {
+    url: &'static str,
+}
+
+const WEED_VERSION: &str = "3.71";
+
+/// Like a shell would: scan `PATH` for `weed`/`weed.exe`.
+pub fn find_weed_on_path() -> Option<PathBuf> {
+    let exe_name = if cfg!(windows) { "weed.exe" } else { "weed" };
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var)
+        .map(|dir| dir.join(exe_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// Pinned SeaweedFS release used for managed downloads. The version appears
+/// twice: in `WEED_VERSION` (used for the checksums URL) and as the literal
+/// segment in each asset URL below (`concat!` can't read a const), so both
+/// must be bumped together when upgrading. Checksums are not hardcoded here:
+/// we fetch the upstream `checksums.txt` published alongside each release and
+/// verify against that.
+fn release_asset_for_platform() -> anyhow::Result<ReleaseAsset> {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("windows", "x86_64") => Ok(ReleaseAsset {
+            url: concat!(
+                "https://github.com/seaweedfs/seaweedfs/releases/download/",
+                "3.71",
+                "/weed_windows_amd64.zip"
+            ),
+        }),
+        ("linux", "x86_64") => Ok(ReleaseAsset {
+            url: concat!(
+                "https://github.com/seaweedfs/seaweedfs/releases/download/",
+                "3.71",
+                "/linux_amd64.tar.gz"
+            ),
+        }),
+        ("macos", "aarch64") => Ok(ReleaseAsset {
+            url: concat!(
+                "https://github.com/seaweedfs/seaweedfs/releases/download/",
+                "3.71",
+                "/darwin_arm64.tar.gz"
+            ),
+        }),
+        ("macos", "x86_64") => Ok(ReleaseAsset {
+            url: concat!(
+                "https://github.com/seaweedfs/seaweedfs/releases/download/",
+                "3.71",
+                "/darwin_amd64.tar.gz"
+            ),
+        }),
+        (os, arch) => anyhow::bail!(
+            "No managed SeaweedFS release is published for {os}/{arch}; set seaweed_weed_path manually"
+        ),
+    }
+}
+
+/// Fetch the upstream `checksums.txt` for the pinned release and return the
+/// expected SHA-256 for `archive_name`, so we verify against what SeaweedFS
+/// actually published instead of a value baked into this binary.
+async fn fetch_upstream_sha256(archive_name: &str) -> anyhow::Result<String> {
+    let checksums_url = format!(
+        "https://github.com/seaweedfs/seaweedfs/releases/download/{WEED_VERSION}/checksums.txt"
+    );
+    let body = reqwest::get(&checksums_url)
+        .await
+        .context("Failed to reach the SeaweedFS release server for checksums")?
+        .text()
+        .await
+        .context("Failed to read the SeaweedFS checksums file")?;
+
+    for line in body.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(hash) = parts.next() else { continue };
+        let Some(name) = parts.next() else { continue };
+        if name.trim_start_matches('*') == archive_name {
+            return Ok(hash.to_lowercase());
+        }
+    }
+
+    anyhow::bail!("No checksum entry for {archive_name} in upstream checksums.txt")
+}
+
+fn extract_weed_binary(bytes: &[u8], archive_name: &str, tools_dir: &Path) -> anyhow::Result<PathBuf> {
+    let weed_name = if cfg!(windows) { "weed.exe" } else { "weed" };
+    let dest = tools_dir.join(weed_name);
+
+    if archive_name.ends_with(".zip") {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+        let mut entry = archive
+            .by_name(weed_name)
+            .context("weed binary missing from downloaded zip")?;
+        let mut out = fs::File::create(&dest)?;
+        std::io::copy(&mut entry, &mut out)?;
+    } else {
+        let decoder = flate2::read::GzDecoder::new(bytes);
+        let mut tar = tar::Archive::new(decoder);
+        let mut found = false;
+        for entry in tar.entries()? {
+            let mut entry = entry?;
+            if entry.path()?.file_name().and_then(|n| n.to_str()) == Some(weed_name) {
+                entry.unpack(&dest)?;
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            anyhow::bail!("weed binary missing from downloaded tarball");
+        }
+    }
+
+    Ok(dest)
+}
+
+/// Download the pinned `weed` release for the current OS/arch into
+/// `FACEFORGE_HOME/tools`, verifying it against the SHA-256 listed in the
+/// release's upstream `checksums.txt` (fetched at download time; no checksum
+/// is stored in this binary) before extracting it and returning the path.
+/// Emits `weed-download-progress` events as bytes land.
+pub async fn download_weed(app: AppHandle, faceforge_home: &Path) -> anyhow::Result<PathBuf> {
+    use futures_util::StreamExt;
+
+    let asset = release_asset_for_platform()?;
+    let tools_dir = faceforge_home.join("tools");
+    fs::create_dir_all(&tools_dir)?;
+
+    let response = reqwest::get(asset.url)
+        .await
+        .context("Failed to reach the SeaweedFS release server")?;
+    let total = response.content_length();
+
+    let mut downloaded: u64 = 0;
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Download interrupted")?;
+        downloaded += chunk.len() as u64;
+        bytes.extend_from_slice(&chunk);
+        let _ = app.emit(
+            "weed-download-progress",
+            DownloadProgress { downloaded, total },
+        );
+    }
+
+    let archive_name = asset.url.rsplit('/').next().unwrap_or(asset.url);
+    let expected_sha256 = fetch_upstream_sha256(archive_name).await?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    let digest = format!("{:x}", hasher.finalize());
+    if digest != expected_sha256 {
+        anyhow::bail!(
+            "Downloaded SeaweedFS asset failed checksum verification (expected {}, got {})",
+            expected_sha256,
+            digest
+        );
+    }
+
+    let dest = extract_weed_binary(&bytes, archive_name, &tools_dir)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&dest)?.permissions();
+        perms.set_mode(perms.mode() | 0o111);
+        fs::set_permissions(&dest, perms)?;
+    }
+
+    Ok(dest)
+}